@@ -0,0 +1,176 @@
+//! Async variant of [`crate::JSBSim`] built on Tokio.
+//!
+//! [`AsyncJSBSim`] mirrors the synchronous client's API but never blocks the
+//! executor, so a single task can drive many simulators concurrently with
+//! `join!`/`select!` instead of needing a thread per instance.
+
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+use tokio::process::{Child, Command};
+
+use crate::{GetError, JSBSimProcessProperties};
+
+/// An async, Tokio-backed JSBSim client.
+///
+/// Holds the active TCP `connection` to the simulator console and an
+/// optional owned `process` when the client started JSBSim itself, just like
+/// [`crate::JSBSim`].
+pub struct AsyncJSBSim {
+    connection: BufReader<TcpStream>,
+    process: Option<Child>,
+}
+
+impl AsyncJSBSim {
+    /// Connect to an already-running JSBSim TCP server at `address`.
+    pub async fn new(address: &str) -> std::io::Result<Self> {
+        let stream = TcpStream::connect(address).await?;
+        let mut jsbsim = AsyncJSBSim {
+            connection: BufReader::new(stream),
+            process: None,
+        };
+        jsbsim.read_line().await?;
+        Ok(jsbsim)
+    }
+
+    /// Spawn a new JSBSim process with the given `properties` and connect to it.
+    ///
+    /// Behaves like [`crate::JSBSim::new_with_process`], but the wait for the
+    /// "JSBSim Execution beginning" banner and the console connection are
+    /// both non-blocking.
+    pub async fn new_with_process(properties: JSBSimProcessProperties) -> std::io::Result<Self> {
+        let port = properties.port();
+        let mut command = Command::new(properties.executable_name());
+        command
+            .stdout(std::process::Stdio::piped())
+            .args(properties.command_args()?);
+
+        let mut process = command.spawn()?;
+
+        let stdout = process.stdout.take().unwrap();
+        let mut reader = tokio::io::BufReader::new(stdout);
+        let mut line = String::new();
+        loop {
+            line.clear();
+            let bytes_read = reader.read_line(&mut line).await?;
+            if bytes_read == 0 {
+                break; // EOF
+            }
+            if line.contains("JSBSim Execution beginning") {
+                break;
+            }
+        }
+
+        let address = format!("localhost:{port}", port = port);
+        match TcpStream::connect(address).await {
+            Ok(stream) => {
+                let mut jsbsim = AsyncJSBSim {
+                    connection: BufReader::new(stream),
+                    process: Some(process),
+                };
+                jsbsim.read_line().await?;
+                Ok(jsbsim)
+            }
+            Err(e) => {
+                let _ = process.start_kill();
+                let _ = process.wait().await;
+                Err(e)
+            }
+        }
+    }
+
+    /// Read one logical response line from the JSBSim console.
+    async fn read_line(&mut self) -> std::io::Result<String> {
+        let mut response = String::new();
+        self.connection.read_line(&mut response).await?;
+
+        while response.trim().is_empty() || response.trim() == "JSBSim>" {
+            response.clear();
+            self.connection.read_line(&mut response).await?;
+        }
+        Ok(response)
+    }
+
+    /// Ask JSBSim to enter the suspended "hold" state.
+    pub async fn hold(&mut self) -> std::io::Result<()> {
+        self.send_command("hold\n").await?;
+        self.read_line().await.map(|_| ())
+    }
+
+    /// Resume simulation execution after a hold.
+    pub async fn resume(&mut self) -> std::io::Result<()> {
+        self.send_command("resume\n").await?;
+        let line = self.read_line().await?;
+        if !line.trim().ends_with("Resuming") {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                format!("Failed to resume: {}", line.trim()),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Advance the simulation by `steps` iterations and verify success.
+    pub async fn iterate(&mut self, steps: i32) -> std::io::Result<()> {
+        self.send_command(&format!("iterate {steps}\n", steps = steps))
+            .await?;
+        let line = self.read_line().await?;
+        if !line.trim().ends_with("Iterations performed") {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                format!("Failed to iterate: {}", line.trim()),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Set a simulator property `key` to `value`.
+    ///
+    /// The function asserts that JSBSim acknowledged the change with
+    /// `set successful`.
+    pub async fn set(&mut self, key: &str, value: impl std::fmt::Display) -> std::io::Result<()> {
+        self.send_command(&format!("set {key} {value}\n")).await?;
+        let line = self.read_line().await?;
+        if !line.trim().ends_with("set successful") {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                format!("Failed to set property: {}", line.trim()),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Get the value of `key` from JSBSim and parse it into `T`.
+    ///
+    /// JSBSim replies with `key = value`; the RHS is parsed and returned or
+    /// an error is produced if parsing fails.
+    pub async fn get<T: std::str::FromStr + std::fmt::Debug>(
+        &mut self,
+        key: &str,
+    ) -> Result<T, GetError<T>>
+    where
+        <T as std::str::FromStr>::Err: std::fmt::Debug,
+    {
+        self.send_command(&format!("get {key}\n")).await?;
+        let response = self.read_line().await?;
+        crate::parse_property_response(&response)
+    }
+
+    /// Send a raw command string to JSBSim.
+    async fn send_command(&mut self, command: &str) -> std::io::Result<()> {
+        self.connection.write_all(command.as_bytes()).await
+    }
+}
+
+impl Drop for AsyncJSBSim {
+    /// Best-effort termination of any spawned process.
+    ///
+    /// Unlike [`crate::JSBSim`], the "quit" console command is not sent on
+    /// drop: that would require an async write, which `Drop` cannot perform.
+    /// The client has no public method that sends "quit"; a spawned process
+    /// is killed with `start_kill` and is not waited on.
+    fn drop(&mut self) {
+        if let Some(process) = &mut self.process {
+            let _ = process.start_kill();
+        }
+    }
+}
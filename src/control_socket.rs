@@ -0,0 +1,88 @@
+//! Push actuator inputs into JSBSim over a dedicated socket at frame rate.
+//!
+//! Mirrors the ArduPilot-style SITL split: control surface commands are
+//! pushed into a JSBSim `<input type="SOCKET">` port each step, while
+//! vehicle state comes back separately via [`crate::FdmListener`]. This
+//! avoids issuing one `set` console command per surface, which is far too
+//! slow to drive a closed loop at hundreds of Hz.
+
+use std::net::UdpSocket;
+
+/// A single frame of control surface commands.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Controls {
+    /// Aileron deflection, -1.0 (full left) to 1.0 (full right).
+    pub aileron: f64,
+    /// Elevator deflection, -1.0 (full down) to 1.0 (full up).
+    pub elevator: f64,
+    /// Throttle, 0.0 to 1.0.
+    pub throttle: f64,
+    /// Rudder deflection, -1.0 (full left) to 1.0 (full right).
+    pub rudder: f64,
+}
+
+impl Controls {
+    /// Encode this frame as `<input type="SOCKET">` lines.
+    ///
+    /// JSBSim's socket input handler accepts the same textual protocol as
+    /// its console, so this is just one `set <property> <value>` line per
+    /// control, exactly like [`crate::JSBSim::set`].
+    fn to_frame(self) -> String {
+        format!(
+            "set fcs/aileron-cmd-norm {aileron}\n\
+             set fcs/elevator-cmd-norm {elevator}\n\
+             set fcs/throttle-cmd-norm {throttle}\n\
+             set fcs/rudder-cmd-norm {rudder}\n",
+            aileron = self.aileron,
+            elevator = self.elevator,
+            throttle = self.throttle,
+            rudder = self.rudder,
+        )
+    }
+}
+
+/// A UDP connection to a JSBSim `<input type="SOCKET">` port.
+///
+/// Sends a full frame of control values as a single datagram each step,
+/// rather than one console `set` per surface.
+pub struct ControlSocket {
+    socket: UdpSocket,
+}
+
+impl ControlSocket {
+    /// Bind `local_addr` and connect to JSBSim's input socket at `remote_addr`.
+    pub fn connect(local_addr: &str, remote_addr: &str) -> std::io::Result<Self> {
+        let socket = UdpSocket::bind(local_addr)?;
+        socket.connect(remote_addr)?;
+        Ok(ControlSocket { socket })
+    }
+
+    /// Push a frame of control values to JSBSim.
+    pub fn apply_controls(&self, controls: Controls) -> std::io::Result<()> {
+        self.socket.send(controls.to_frame().as_bytes())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_frame_emits_one_set_command_per_control() {
+        let controls = Controls {
+            aileron: 0.1,
+            elevator: -0.2,
+            throttle: 0.75,
+            rudder: 0.0,
+        };
+
+        assert_eq!(
+            controls.to_frame(),
+            "set fcs/aileron-cmd-norm 0.1\n\
+             set fcs/elevator-cmd-norm -0.2\n\
+             set fcs/throttle-cmd-norm 0.75\n\
+             set fcs/rudder-cmd-norm 0\n"
+        );
+    }
+}
@@ -0,0 +1,591 @@
+//! Decoder for JSBSim's FlightGear-compatible binary FDM output stream.
+//!
+//! When JSBSim is configured with an `<output type="FLIGHTGEAR">` block it
+//! emits a fixed-layout `FGNetFDM` struct over UDP once per frame, giving the
+//! full vehicle state at simulation rate without per-property console
+//! round-trips. [`FdmListener`] receives and decodes these datagrams into
+//! [`FdmState`].
+
+use std::net::UdpSocket;
+
+/// The `FGNetFDM` protocol version this decoder understands.
+pub const FG_NET_FDM_VERSION: u32 = 24;
+
+const FG_MAX_ENGINES: usize = 4;
+const FG_MAX_WHEELS: usize = 3;
+const FG_MAX_TANKS: usize = 4;
+
+/// Error decoding or receiving an `FGNetFDM` packet.
+#[derive(Debug)]
+pub enum FdmError {
+    /// Underlying IO error while receiving from the socket.
+    IoError(std::io::Error),
+    /// The packet's version word did not match [`FG_NET_FDM_VERSION`].
+    UnexpectedVersion { expected: u32, found: u32 },
+    /// The datagram was smaller than a full `FGNetFDM` struct.
+    Truncated { expected: usize, found: usize },
+}
+
+impl From<std::io::Error> for FdmError {
+    fn from(error: std::io::Error) -> Self {
+        FdmError::IoError(error)
+    }
+}
+
+/// Decoded JSBSim/FlightGear `FGNetFDM` vehicle state.
+///
+/// Angles are in radians, positions in meters/radians as noted per field,
+/// matching the wire format. All fields are decoded from big-endian (network
+/// byte order) as sent by JSBSim.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FdmState {
+    /// Protocol version, always [`FG_NET_FDM_VERSION`] for a packet this
+    /// decoder accepted.
+    pub version: u32,
+    /// Geodetic longitude, radians.
+    pub longitude: f64,
+    /// Geodetic latitude, radians.
+    pub latitude: f64,
+    /// Altitude above sea level, meters.
+    pub altitude: f64,
+    /// Altitude above ground level, meters.
+    pub agl: f32,
+    /// Roll angle, radians.
+    pub phi: f32,
+    /// Pitch angle, radians.
+    pub theta: f32,
+    /// Yaw angle, radians.
+    pub psi: f32,
+    /// Angle of attack, radians.
+    pub alpha: f32,
+    /// Sideslip angle, radians.
+    pub beta: f32,
+    /// Roll rate, radians/sec.
+    pub phidot: f32,
+    /// Pitch rate, radians/sec.
+    pub thetadot: f32,
+    /// Yaw rate, radians/sec.
+    pub psidot: f32,
+    /// Calibrated airspeed, knots.
+    pub vcas: f32,
+    /// Climb rate, ft/sec.
+    pub climb_rate: f32,
+    /// Velocity north, ft/sec.
+    pub v_north: f32,
+    /// Velocity east, ft/sec.
+    pub v_east: f32,
+    /// Velocity down, ft/sec.
+    pub v_down: f32,
+    /// Body-axis velocity u, ft/sec.
+    pub v_body_u: f32,
+    /// Body-axis velocity v, ft/sec.
+    pub v_body_v: f32,
+    /// Body-axis velocity w, ft/sec.
+    pub v_body_w: f32,
+    /// Pilot-eyepoint body-axis x acceleration, ft/sec^2.
+    pub a_x_pilot: f32,
+    /// Pilot-eyepoint body-axis y acceleration, ft/sec^2.
+    pub a_y_pilot: f32,
+    /// Pilot-eyepoint body-axis z acceleration, ft/sec^2.
+    pub a_z_pilot: f32,
+    /// Stall warning, 0.0 to 1.0.
+    pub stall_warning: f32,
+    /// Slip ball deflection, degrees.
+    pub slip_deg: f32,
+    /// Number of valid entries in the per-engine arrays below.
+    pub num_engines: u32,
+    /// Per-engine running state (0 = off, 1 = running).
+    pub eng_state: [u32; FG_MAX_ENGINES],
+    /// Per-engine RPM.
+    pub rpm: [f32; FG_MAX_ENGINES],
+    /// Per-engine fuel flow, gallons/hr.
+    pub fuel_flow: [f32; FG_MAX_ENGINES],
+    /// Per-engine fuel pressure, psi.
+    pub fuel_px: [f32; FG_MAX_ENGINES],
+    /// Per-engine exhaust gas temperature, deg F.
+    pub egt: [f32; FG_MAX_ENGINES],
+    /// Per-engine cylinder head temperature, deg F.
+    pub cht: [f32; FG_MAX_ENGINES],
+    /// Per-engine manifold pressure.
+    pub mp_osi: [f32; FG_MAX_ENGINES],
+    /// Per-engine turbine inlet temperature.
+    pub tit: [f32; FG_MAX_ENGINES],
+    /// Per-engine oil temperature, deg F.
+    pub oil_temp: [f32; FG_MAX_ENGINES],
+    /// Per-engine oil pressure, psi.
+    pub oil_px: [f32; FG_MAX_ENGINES],
+    /// Number of valid entries in `fuel_quantity`.
+    pub num_tanks: u32,
+    /// Per-tank fuel quantity, lbs.
+    pub fuel_quantity: [f32; FG_MAX_TANKS],
+    /// Number of valid entries in the per-wheel arrays below.
+    pub num_wheels: u32,
+    /// Per-wheel weight-on-wheels flag (0 = airborne, 1 = on ground).
+    pub wow: [u32; FG_MAX_WHEELS],
+    /// Per-wheel gear extension, 0.0 (up) to 1.0 (down).
+    pub gear_position: [f32; FG_MAX_WHEELS],
+    /// Per-wheel steering angle, degrees.
+    pub gear_steer: [f32; FG_MAX_WHEELS],
+    /// Per-wheel strut compression.
+    pub gear_compression: [f32; FG_MAX_WHEELS],
+    /// Current Unix time, seconds.
+    pub cur_time: u32,
+    /// Offset in seconds to Unix time.
+    pub warp: i32,
+    /// Visibility, meters.
+    pub visibility: f32,
+    /// Elevator deflection, normalized.
+    pub elevator: f32,
+    /// Elevator trim tab deflection, normalized.
+    pub elevator_trim_tab: f32,
+    /// Left flap deflection, normalized.
+    pub left_flap: f32,
+    /// Right flap deflection, normalized.
+    pub right_flap: f32,
+    /// Left aileron deflection, normalized.
+    pub left_aileron: f32,
+    /// Right aileron deflection, normalized.
+    pub right_aileron: f32,
+    /// Rudder deflection, normalized.
+    pub rudder: f32,
+    /// Nose wheel steering angle, normalized.
+    pub nose_wheel: f32,
+    /// Speedbrake deflection, normalized.
+    pub speedbrake: f32,
+    /// Spoiler deflection, normalized.
+    pub spoilers: f32,
+}
+
+/// Incrementally reads big-endian fields out of a byte slice.
+struct BigEndianReader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> BigEndianReader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        BigEndianReader { bytes, offset: 0 }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], FdmError> {
+        let end = self.offset + len;
+        if end > self.bytes.len() {
+            return Err(FdmError::Truncated {
+                expected: end,
+                found: self.bytes.len(),
+            });
+        }
+        let slice = &self.bytes[self.offset..end];
+        self.offset = end;
+        Ok(slice)
+    }
+
+    fn u32(&mut self) -> Result<u32, FdmError> {
+        Ok(u32::from_be_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn i32(&mut self) -> Result<i32, FdmError> {
+        Ok(i32::from_be_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn f32(&mut self) -> Result<f32, FdmError> {
+        Ok(f32::from_be_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn f64(&mut self) -> Result<f64, FdmError> {
+        Ok(f64::from_be_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn f32_array<const N: usize>(&mut self) -> Result<[f32; N], FdmError> {
+        let mut out = [0.0f32; N];
+        for slot in &mut out {
+            *slot = self.f32()?;
+        }
+        Ok(out)
+    }
+
+    fn u32_array<const N: usize>(&mut self) -> Result<[u32; N], FdmError> {
+        let mut out = [0u32; N];
+        for slot in &mut out {
+            *slot = self.u32()?;
+        }
+        Ok(out)
+    }
+}
+
+impl FdmState {
+    /// Decode a raw `FGNetFDM` datagram.
+    ///
+    /// Returns [`FdmError::UnexpectedVersion`] if the version word does not
+    /// match [`FG_NET_FDM_VERSION`], and [`FdmError::Truncated`] if the
+    /// packet is shorter than expected.
+    pub fn decode(datagram: &[u8]) -> Result<Self, FdmError> {
+        let mut reader = BigEndianReader::new(datagram);
+
+        let version = reader.u32()?;
+        if version != FG_NET_FDM_VERSION {
+            return Err(FdmError::UnexpectedVersion {
+                expected: FG_NET_FDM_VERSION,
+                found: version,
+            });
+        }
+        let _padding = reader.u32()?;
+
+        let longitude = reader.f64()?;
+        let latitude = reader.f64()?;
+        let altitude = reader.f64()?;
+        let agl = reader.f32()?;
+
+        let phi = reader.f32()?;
+        let theta = reader.f32()?;
+        let psi = reader.f32()?;
+
+        let alpha = reader.f32()?;
+        let beta = reader.f32()?;
+
+        let phidot = reader.f32()?;
+        let thetadot = reader.f32()?;
+        let psidot = reader.f32()?;
+
+        let vcas = reader.f32()?;
+        let climb_rate = reader.f32()?;
+
+        let v_north = reader.f32()?;
+        let v_east = reader.f32()?;
+        let v_down = reader.f32()?;
+
+        let v_body_u = reader.f32()?;
+        let v_body_v = reader.f32()?;
+        let v_body_w = reader.f32()?;
+
+        let a_x_pilot = reader.f32()?;
+        let a_y_pilot = reader.f32()?;
+        let a_z_pilot = reader.f32()?;
+
+        let stall_warning = reader.f32()?;
+        let slip_deg = reader.f32()?;
+
+        let num_engines = reader.u32()?;
+        let eng_state = reader.u32_array::<FG_MAX_ENGINES>()?;
+        let rpm = reader.f32_array::<FG_MAX_ENGINES>()?;
+        let fuel_flow = reader.f32_array::<FG_MAX_ENGINES>()?;
+        let fuel_px = reader.f32_array::<FG_MAX_ENGINES>()?;
+        let egt = reader.f32_array::<FG_MAX_ENGINES>()?;
+        let cht = reader.f32_array::<FG_MAX_ENGINES>()?;
+        let mp_osi = reader.f32_array::<FG_MAX_ENGINES>()?;
+        let tit = reader.f32_array::<FG_MAX_ENGINES>()?;
+        let oil_temp = reader.f32_array::<FG_MAX_ENGINES>()?;
+        let oil_px = reader.f32_array::<FG_MAX_ENGINES>()?;
+
+        let num_tanks = reader.u32()?;
+        let fuel_quantity = reader.f32_array::<FG_MAX_TANKS>()?;
+
+        let num_wheels = reader.u32()?;
+        let wow = reader.u32_array::<FG_MAX_WHEELS>()?;
+        let gear_position = reader.f32_array::<FG_MAX_WHEELS>()?;
+        let gear_steer = reader.f32_array::<FG_MAX_WHEELS>()?;
+        let gear_compression = reader.f32_array::<FG_MAX_WHEELS>()?;
+
+        let cur_time = reader.u32()?;
+        let warp = reader.i32()?;
+        let visibility = reader.f32()?;
+
+        let elevator = reader.f32()?;
+        let elevator_trim_tab = reader.f32()?;
+        let left_flap = reader.f32()?;
+        let right_flap = reader.f32()?;
+        let left_aileron = reader.f32()?;
+        let right_aileron = reader.f32()?;
+        let rudder = reader.f32()?;
+        let nose_wheel = reader.f32()?;
+        let speedbrake = reader.f32()?;
+        let spoilers = reader.f32()?;
+
+        Ok(FdmState {
+            version,
+            longitude,
+            latitude,
+            altitude,
+            agl,
+            phi,
+            theta,
+            psi,
+            alpha,
+            beta,
+            phidot,
+            thetadot,
+            psidot,
+            vcas,
+            climb_rate,
+            v_north,
+            v_east,
+            v_down,
+            v_body_u,
+            v_body_v,
+            v_body_w,
+            a_x_pilot,
+            a_y_pilot,
+            a_z_pilot,
+            stall_warning,
+            slip_deg,
+            num_engines,
+            eng_state,
+            rpm,
+            fuel_flow,
+            fuel_px,
+            egt,
+            cht,
+            mp_osi,
+            tit,
+            oil_temp,
+            oil_px,
+            num_tanks,
+            fuel_quantity,
+            num_wheels,
+            wow,
+            gear_position,
+            gear_steer,
+            gear_compression,
+            cur_time,
+            warp,
+            visibility,
+            elevator,
+            elevator_trim_tab,
+            left_flap,
+            right_flap,
+            left_aileron,
+            right_aileron,
+            rudder,
+            nose_wheel,
+            speedbrake,
+            spoilers,
+        })
+    }
+}
+
+/// Receives and decodes JSBSim's FlightGear-format FDM UDP stream.
+pub struct FdmListener {
+    socket: UdpSocket,
+    buffer: [u8; 1500],
+}
+
+impl FdmListener {
+    /// Bind a UDP socket at `addr` to receive FDM datagrams on.
+    ///
+    /// `addr` should match the host/port JSBSim's `<output type="FLIGHTGEAR">`
+    /// block (or [`crate::JSBSimProcessProperties`]'s FDM output fields) is
+    /// configured to send to.
+    pub fn bind(addr: &str) -> std::io::Result<Self> {
+        let socket = UdpSocket::bind(addr)?;
+        Ok(FdmListener {
+            socket,
+            buffer: [0u8; 1500],
+        })
+    }
+
+    /// Block until the next datagram arrives and decode it.
+    pub fn recv(&mut self) -> Result<FdmState, FdmError> {
+        let (len, _addr) = self.socket.recv_from(&mut self.buffer)?;
+        FdmState::decode(&self.buffer[..len])
+    }
+
+    /// Decode the next datagram if one is already available, without
+    /// blocking.
+    ///
+    /// Returns `Ok(None)` if no datagram is currently pending.
+    pub fn try_recv(&mut self) -> Result<Option<FdmState>, FdmError> {
+        self.socket.set_nonblocking(true)?;
+        let result = self.socket.recv_from(&mut self.buffer);
+        self.socket.set_nonblocking(false)?;
+
+        match result {
+            Ok((len, _addr)) => Ok(Some(FdmState::decode(&self.buffer[..len])?)),
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => Ok(None),
+            Err(e) => Err(FdmError::IoError(e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn push_u32(buf: &mut Vec<u8>, v: u32) {
+        buf.extend_from_slice(&v.to_be_bytes());
+    }
+    fn push_i32(buf: &mut Vec<u8>, v: i32) {
+        buf.extend_from_slice(&v.to_be_bytes());
+    }
+    fn push_f32(buf: &mut Vec<u8>, v: f32) {
+        buf.extend_from_slice(&v.to_be_bytes());
+    }
+    fn push_f64(buf: &mut Vec<u8>, v: f64) {
+        buf.extend_from_slice(&v.to_be_bytes());
+    }
+
+    /// Build a byte-exact `FGNetFDM` v24 packet matching the real
+    /// `net_fdm.hxx` layout, to catch any field-order regression.
+    fn sample_packet() -> Vec<u8> {
+        let mut buf = Vec::new();
+        push_u32(&mut buf, FG_NET_FDM_VERSION);
+        push_u32(&mut buf, 0); // padding
+
+        push_f64(&mut buf, 1.1); // longitude
+        push_f64(&mut buf, 2.2); // latitude
+        push_f64(&mut buf, 3.3); // altitude
+        push_f32(&mut buf, 4.4); // agl
+        push_f32(&mut buf, 5.5); // phi
+        push_f32(&mut buf, 6.6); // theta
+        push_f32(&mut buf, 7.7); // psi
+        push_f32(&mut buf, 8.8); // alpha
+        push_f32(&mut buf, 9.9); // beta
+
+        push_f32(&mut buf, 10.0); // phidot
+        push_f32(&mut buf, 11.0); // thetadot
+        push_f32(&mut buf, 12.0); // psidot
+        push_f32(&mut buf, 13.0); // vcas
+        push_f32(&mut buf, 14.0); // climb_rate
+        push_f32(&mut buf, 15.0); // v_north
+        push_f32(&mut buf, 16.0); // v_east
+        push_f32(&mut buf, 17.0); // v_down
+        push_f32(&mut buf, 18.0); // v_body_u
+        push_f32(&mut buf, 19.0); // v_body_v
+        push_f32(&mut buf, 20.0); // v_body_w
+
+        push_f32(&mut buf, 21.0); // a_x_pilot
+        push_f32(&mut buf, 22.0); // a_y_pilot
+        push_f32(&mut buf, 23.0); // a_z_pilot
+
+        push_f32(&mut buf, 0.5); // stall_warning
+        push_f32(&mut buf, 1.5); // slip_deg
+
+        push_u32(&mut buf, 2); // num_engines
+        for v in [1u32, 0, 0, 0] {
+            push_u32(&mut buf, v); // eng_state
+        }
+        for v in [2500.0f32, 0.0, 0.0, 0.0] {
+            push_f32(&mut buf, v); // rpm
+        }
+        for v in [10.0f32, 0.0, 0.0, 0.0] {
+            push_f32(&mut buf, v); // fuel_flow
+        }
+        for v in [30.0f32, 0.0, 0.0, 0.0] {
+            push_f32(&mut buf, v); // fuel_px
+        }
+        for v in [1200.0f32, 0.0, 0.0, 0.0] {
+            push_f32(&mut buf, v); // egt
+        }
+        for v in [380.0f32, 0.0, 0.0, 0.0] {
+            push_f32(&mut buf, v); // cht
+        }
+        for v in [25.0f32, 0.0, 0.0, 0.0] {
+            push_f32(&mut buf, v); // mp_osi
+        }
+        for v in [1500.0f32, 0.0, 0.0, 0.0] {
+            push_f32(&mut buf, v); // tit
+        }
+        for v in [180.0f32, 0.0, 0.0, 0.0] {
+            push_f32(&mut buf, v); // oil_temp
+        }
+        for v in [60.0f32, 0.0, 0.0, 0.0] {
+            push_f32(&mut buf, v); // oil_px
+        }
+
+        push_u32(&mut buf, 1); // num_tanks
+        for v in [100.0f32, 0.0, 0.0, 0.0] {
+            push_f32(&mut buf, v); // fuel_quantity
+        }
+
+        push_u32(&mut buf, 3); // num_wheels
+        for v in [1u32, 1, 0] {
+            push_u32(&mut buf, v); // wow
+        }
+        for v in [1.0f32, 1.0, 0.0] {
+            push_f32(&mut buf, v); // gear_position
+        }
+        for v in [0.0f32, 0.0, 2.0] {
+            push_f32(&mut buf, v); // gear_steer
+        }
+        for v in [0.1f32, 0.1, 0.0] {
+            push_f32(&mut buf, v); // gear_compression
+        }
+
+        push_u32(&mut buf, 1_700_000_000); // cur_time
+        push_i32(&mut buf, -5); // warp
+        push_f32(&mut buf, 16000.0); // visibility
+
+        push_f32(&mut buf, 0.1); // elevator
+        push_f32(&mut buf, 0.0); // elevator_trim_tab
+        push_f32(&mut buf, 0.0); // left_flap
+        push_f32(&mut buf, 0.0); // right_flap
+        push_f32(&mut buf, 0.2); // left_aileron
+        push_f32(&mut buf, -0.2); // right_aileron
+        push_f32(&mut buf, 0.0); // rudder
+        push_f32(&mut buf, 0.0); // nose_wheel
+        push_f32(&mut buf, 0.0); // speedbrake
+        push_f32(&mut buf, 0.0); // spoilers
+
+        buf
+    }
+
+    #[test]
+    fn decodes_a_real_fgnetfdm_packet() {
+        let state = FdmState::decode(&sample_packet()).expect("decode");
+
+        assert_eq!(state.version, FG_NET_FDM_VERSION);
+        assert_eq!(state.longitude, 1.1);
+        assert_eq!(state.latitude, 2.2);
+        assert_eq!(state.altitude, 3.3);
+        assert_eq!(state.a_z_pilot, 23.0);
+        assert_eq!(state.stall_warning, 0.5);
+        assert_eq!(state.slip_deg, 1.5);
+
+        assert_eq!(state.num_engines, 2);
+        assert_eq!(state.rpm[0], 2500.0);
+        assert_eq!(state.fuel_flow[0], 10.0);
+        assert_eq!(state.oil_px[0], 60.0);
+
+        assert_eq!(state.num_tanks, 1);
+        assert_eq!(state.fuel_quantity[0], 100.0);
+
+        assert_eq!(state.num_wheels, 3);
+        assert_eq!(state.wow, [1, 1, 0]);
+        assert_eq!(state.gear_position, [1.0, 1.0, 0.0]);
+        assert_eq!(state.gear_steer[2], 2.0);
+        assert_eq!(state.gear_compression[0], 0.1);
+
+        assert_eq!(state.cur_time, 1_700_000_000);
+        assert_eq!(state.warp, -5);
+        assert_eq!(state.visibility, 16000.0);
+
+        assert_eq!(state.elevator, 0.1);
+        assert_eq!(state.left_aileron, 0.2);
+        assert_eq!(state.right_aileron, -0.2);
+        assert_eq!(state.spoilers, 0.0);
+    }
+
+    #[test]
+    fn rejects_mismatched_version() {
+        let mut buf = Vec::new();
+        push_u32(&mut buf, FG_NET_FDM_VERSION + 1);
+        push_u32(&mut buf, 0);
+
+        let err = FdmState::decode(&buf).expect_err("should reject mismatched version");
+        assert!(matches!(
+            err,
+            FdmError::UnexpectedVersion { expected, found }
+                if expected == FG_NET_FDM_VERSION && found == FG_NET_FDM_VERSION + 1
+        ));
+    }
+
+    #[test]
+    fn rejects_truncated_packet() {
+        let full = sample_packet();
+        let truncated = &full[..full.len() - 4];
+
+        assert!(matches!(
+            FdmState::decode(truncated),
+            Err(FdmError::Truncated { .. })
+        ));
+    }
+}
@@ -0,0 +1,147 @@
+//! Programmatic initial-condition generation for [`crate::JSBSim::reset_to`].
+//!
+//! Models the common SITL pattern of filling a `reset_template.xml` with
+//! latitude/longitude/heading/altitude before launch, so callers can place
+//! (or replace) an aircraft without shipping a pre-baked IC file alongside
+//! the aircraft definition.
+
+/// Whether a latitude value is geodetic or geocentric.
+///
+/// JSBSim's IC files default to geocentric latitude, but most callers
+/// actually mean geodetic (the latitude reported by GPS/maps) — getting this
+/// wrong silently places the aircraft at the wrong spot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LatitudeKind {
+    /// Geodetic latitude, as reported by GPS and most maps.
+    Geodetic,
+    /// Geocentric latitude, JSBSim's IC file default.
+    Geocentric,
+}
+
+impl LatitudeKind {
+    fn xml_attr(self) -> &'static str {
+        match self {
+            LatitudeKind::Geodetic => "geod",
+            LatitudeKind::Geocentric => "geocentric",
+        }
+    }
+}
+
+/// A JSBSim initial-conditions specification, written to an `<initialize>`
+/// XML file and loaded via [`crate::JSBSim::reset_to`] or
+/// [`crate::JSBSimProcessProperties`]'s `initial_conditions` option.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InitialConditions {
+    /// Latitude, decimal degrees.
+    pub latitude_deg: f64,
+    /// Whether `latitude_deg` is geodetic or geocentric.
+    pub latitude_kind: LatitudeKind,
+    /// Longitude, decimal degrees.
+    pub longitude_deg: f64,
+    /// Altitude above sea level, feet.
+    pub altitude_ft: f64,
+    /// True heading, decimal degrees.
+    pub heading_deg: f64,
+    /// True airspeed, knots. JSBSim defaults to 0 if left unset.
+    pub velocity_kts: Option<f64>,
+    /// Roll angle, decimal degrees.
+    pub phi_deg: Option<f64>,
+    /// Pitch angle, decimal degrees.
+    pub theta_deg: Option<f64>,
+}
+
+impl InitialConditions {
+    /// Render this configuration as a JSBSim `<initialize>` XML document.
+    pub fn to_xml(&self) -> String {
+        let mut body = String::new();
+        body.push_str(&format!(
+            "  <latitude unit=\"DEG\" type=\"{lat_kind}\">{lat}</latitude>\n",
+            lat_kind = self.latitude_kind.xml_attr(),
+            lat = self.latitude_deg,
+        ));
+        body.push_str(&format!(
+            "  <longitude unit=\"DEG\">{lon}</longitude>\n",
+            lon = self.longitude_deg
+        ));
+        body.push_str(&format!(
+            "  <altitude unit=\"FT\">{alt}</altitude>\n",
+            alt = self.altitude_ft
+        ));
+        body.push_str(&format!(
+            "  <psi unit=\"DEG\">{heading}</psi>\n",
+            heading = self.heading_deg
+        ));
+
+        if let Some(velocity) = self.velocity_kts {
+            body.push_str(&format!("  <vt unit=\"KTS\">{velocity}</vt>\n"));
+        }
+        if let Some(phi) = self.phi_deg {
+            body.push_str(&format!("  <phi unit=\"DEG\">{phi}</phi>\n"));
+        }
+        if let Some(theta) = self.theta_deg {
+            body.push_str(&format!("  <theta unit=\"DEG\">{theta}</theta>\n"));
+        }
+
+        format!("<?xml version=\"1.0\"?>\n<initialize name=\"reset_template\">\n{body}</initialize>\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn minimal() -> InitialConditions {
+        InitialConditions {
+            latitude_deg: 47.449,
+            latitude_kind: LatitudeKind::Geodetic,
+            longitude_deg: -122.311,
+            altitude_ft: 1500.0,
+            heading_deg: 270.0,
+            velocity_kts: None,
+            phi_deg: None,
+            theta_deg: None,
+        }
+    }
+
+    #[test]
+    fn to_xml_uses_geod_attr_for_geodetic_latitude() {
+        let xml = minimal().to_xml();
+        assert!(xml.contains("<latitude unit=\"DEG\" type=\"geod\">47.449</latitude>"));
+        assert!(xml.contains("<longitude unit=\"DEG\">-122.311</longitude>"));
+        assert!(xml.contains("<altitude unit=\"FT\">1500</altitude>"));
+        assert!(xml.contains("<psi unit=\"DEG\">270</psi>"));
+    }
+
+    #[test]
+    fn to_xml_uses_geocentric_attr_for_geocentric_latitude() {
+        let ic = InitialConditions {
+            latitude_kind: LatitudeKind::Geocentric,
+            ..minimal()
+        };
+        assert!(ic
+            .to_xml()
+            .contains("<latitude unit=\"DEG\" type=\"geocentric\">47.449</latitude>"));
+    }
+
+    #[test]
+    fn to_xml_omits_unset_optional_fields() {
+        let xml = minimal().to_xml();
+        assert!(!xml.contains("<vt "));
+        assert!(!xml.contains("<phi "));
+        assert!(!xml.contains("<theta "));
+    }
+
+    #[test]
+    fn to_xml_includes_set_optional_fields() {
+        let ic = InitialConditions {
+            velocity_kts: Some(120.0),
+            phi_deg: Some(5.0),
+            theta_deg: Some(-2.5),
+            ..minimal()
+        };
+        let xml = ic.to_xml();
+        assert!(xml.contains("<vt unit=\"KTS\">120</vt>"));
+        assert!(xml.contains("<phi unit=\"DEG\">5</phi>"));
+        assert!(xml.contains("<theta unit=\"DEG\">-2.5</theta>"));
+    }
+}
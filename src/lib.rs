@@ -4,7 +4,9 @@
 //! JSBSim process and interact with its console via the TCP interface.
 //! It is intended for integration tests and tooling that need programmatic
 //! control of JSBSim (get/set properties, step the simulation, hold/resume,
-//! etc.). The implementation is intentionally minimal and synchronous.
+//! etc.). The implementation is intentionally minimal. An async variant,
+//! [`AsyncJSBSim`], is available for callers that want to drive several
+//! simulators from within an existing Tokio runtime.
 //!
 //! # Examples
 //!
@@ -28,9 +30,19 @@
 
 use std::io::BufRead;
 use std::net::TcpStream;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Stdio;
 
+mod async_client;
+mod control_socket;
+mod fdm;
+mod initial_conditions;
+
+pub use async_client::AsyncJSBSim;
+pub use control_socket::{ControlSocket, Controls};
+pub use fdm::{FdmError, FdmListener, FdmState};
+pub use initial_conditions::{InitialConditions, LatitudeKind};
+
 /// Configuration used when spawning a JSBSim process via
 /// `JSBSim::new_with_process`.
 ///
@@ -55,6 +67,16 @@ pub struct JSBSimProcessProperties {
     realtime: bool,
     /// The port to connect to JSBSim on
     port: u16,
+    /// Host to stream FlightGear-format FDM telemetry to, if any. See
+    /// [`crate::FdmListener`].
+    fdm_output_host: Option<String>,
+    /// UDP port to stream FDM telemetry to.
+    fdm_output_port: u16,
+    /// Rate, in Hz, to emit FDM telemetry at.
+    fdm_output_rate_hz: u32,
+    /// Initial conditions to generate and load on start, taking precedence
+    /// over `init_script` when set. See [`InitialConditions`].
+    initial_conditions: Option<InitialConditions>,
 }
 
 impl Default for JSBSimProcessProperties {
@@ -69,7 +91,108 @@ impl Default for JSBSimProcessProperties {
             suspend_on_start: true,
             realtime: false,
             port: 5556,
+            fdm_output_host: None,
+            fdm_output_port: 5500,
+            fdm_output_rate_hz: 60,
+            initial_conditions: None,
+        }
+    }
+}
+
+impl JSBSimProcessProperties {
+    /// The name of the executable to spawn.
+    pub(crate) fn executable_name(&self) -> &str {
+        &self.executable_name
+    }
+
+    /// The port the spawned process will accept console connections on.
+    pub(crate) fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// The JSBSim root directory.
+    pub(crate) fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// The simulation step size implied by `simulation_hz`, in seconds.
+    pub(crate) fn dt_sec(&self) -> f64 {
+        1.0 / self.simulation_hz as f64
+    }
+
+    /// Build the JSBSim command-line arguments described by these properties.
+    ///
+    /// Shared by the synchronous and async `new_with_process` constructors so
+    /// the two stay in lockstep as fields are added. Writes out any support
+    /// files (such as the FDM output directive) these arguments reference.
+    pub(crate) fn command_args(&self) -> std::io::Result<Vec<String>> {
+        let mut args = vec![
+            format!("--simulation-rate={rate}", rate = self.simulation_hz),
+            format!("--root={root}", root = self.root.display()),
+        ];
+
+        if let Some(aircraft) = &self.aircraft {
+            args.push(format!("--aircraft={aircraft}", aircraft = aircraft));
         }
+
+        if let Some(path) = self.write_initial_conditions_file()? {
+            args.push(format!("--initfile={path}", path = path.display()));
+        } else if let Some(init_script) = &self.init_script {
+            args.push(format!("--initfile={script}", script = init_script));
+        }
+
+        if let Some(script) = &self.script {
+            args.push(format!("--script={script}", script = script));
+        }
+
+        if self.suspend_on_start {
+            args.push("--suspend".to_string());
+        }
+
+        if self.realtime {
+            args.push("--realtime".to_string());
+        }
+
+        if let Some(directive) = self.write_fdm_output_directive()? {
+            args.push(format!(
+                "--output-directive-file={path}",
+                path = directive.display()
+            ));
+        }
+
+        Ok(args)
+    }
+
+    /// Write the `<output type="FLIGHTGEAR">` directive file for
+    /// `fdm_output_host`, if configured, and return its path.
+    fn write_fdm_output_directive(&self) -> std::io::Result<Option<PathBuf>> {
+        let Some(host) = &self.fdm_output_host else {
+            return Ok(None);
+        };
+
+        std::fs::create_dir_all(&self.root)?;
+        let path = self.root.join("fdm_output.xml");
+        let xml = format!(
+            "<?xml version=\"1.0\"?>\n<output name=\"{host}\" type=\"FLIGHTGEAR\" port=\"{port}\" protocol=\"UDP\" rate=\"{rate}\"/>\n",
+            host = host,
+            port = self.fdm_output_port,
+            rate = self.fdm_output_rate_hz
+        );
+        std::fs::write(&path, xml)?;
+        Ok(Some(path))
+    }
+
+    /// Write the generated `<initialize>` IC file for `initial_conditions`,
+    /// if configured, and return its path.
+    fn write_initial_conditions_file(&self) -> std::io::Result<Option<PathBuf>> {
+        let Some(ic) = &self.initial_conditions else {
+            return Ok(None);
+        };
+
+        std::fs::create_dir_all(&self.root)?;
+        let path = self.root.join("generated_ic.xml");
+        std::fs::write(&path, ic.to_xml())?;
+        Ok(Some(path))
     }
 }
 
@@ -81,7 +204,10 @@ impl Default for JSBSimProcessProperties {
 /// common responses.
 pub struct JSBSim {
     connection: TcpStream,
+    reader: std::io::BufReader<TcpStream>,
     process: Option<std::process::Child>,
+    root: PathBuf,
+    dt_sec: f64,
 }
 
 /// Error returned by `JSBSim::get` when retrieving a property value.
@@ -108,6 +234,35 @@ where
     }
 }
 
+/// Parse a single `key = value` console response line into `T`.
+///
+/// Shared by `JSBSim::get` and `JSBSim::get_many`.
+fn parse_property_response<T: std::str::FromStr + std::fmt::Debug>(
+    response: &str,
+) -> Result<T, GetError<T>>
+where
+    <T as std::str::FromStr>::Err: std::fmt::Debug,
+{
+    let collection = response.trim().split('=').collect::<Vec<&str>>();
+    debug_assert!(
+        collection.len() == 2,
+        "Response from JSBSim not in expected format '{}' '{}'",
+        collection.len(),
+        response.trim()
+    );
+    collection
+        .get(1)
+        .ok_or_else(|| {
+            GetError::IoError(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "No value returned",
+            ))
+        })?
+        .trim()
+        .parse::<T>()
+        .map_err(GetError::ParseError)
+}
+
 impl JSBSim {
     /// Connect to an already-running JSBSim TCP server at `address`.
     ///
@@ -116,11 +271,21 @@ impl JSBSim {
     /// returning.
     pub fn new(address: &str) -> std::io::Result<Self> {
         let stream = TcpStream::connect(address)?;
+        let reader = std::io::BufReader::new(stream.try_clone()?);
         let mut jsbsim = JSBSim {
             connection: stream,
+            reader,
             process: None,
+            root: PathBuf::from("."),
+            dt_sec: 0.0,
         };
         jsbsim.read_line()?;
+        jsbsim.dt_sec = jsbsim.get::<f64>("simulation/dt").map_err(|_| {
+            std::io::Error::new(
+                std::io::ErrorKind::Other,
+                "Failed to query simulation/dt",
+            )
+        })?;
         Ok(jsbsim)
     }
 
@@ -131,34 +296,13 @@ impl JSBSim {
     /// It returns a `JSBSim` instance that can be used to interact with the
     /// simulator.
     pub fn new_with_process(properties: JSBSimProcessProperties) -> Result<Self, std::io::Error> {
-        let mut command = std::process::Command::new(properties.executable_name.as_str());
+        let port = properties.port();
+        let root = properties.root().to_path_buf();
+        let dt_sec = properties.dt_sec();
+        let mut command = std::process::Command::new(properties.executable_name());
         command
             .stdout(Stdio::piped())
-            .arg(format!(
-                "--simulation-rate={rate}",
-                rate = properties.simulation_hz
-            ))
-            .arg(format!("--root={root}", root = properties.root.display()));
-
-        if let Some(aircraft) = properties.aircraft {
-            command.arg(format!("--aircraft={aircraft}", aircraft = aircraft));
-        }
-
-        if let Some(init_script) = properties.init_script {
-            command.arg(format!("--initfile={script}", script = init_script));
-        }
-
-        if let Some(script) = properties.script {
-            command.arg(format!("--script={script}", script = script));
-        }
-
-        if properties.suspend_on_start {
-            command.arg("--suspend");
-        }
-
-        if properties.realtime {
-            command.arg("--realtime");
-        }
+            .args(properties.command_args()?);
 
         let mut process = command.spawn()?;
 
@@ -177,12 +321,16 @@ impl JSBSim {
             }
         }
 
-        let address = format!("localhost:{port}", port = properties.port);
+        let address = format!("localhost:{port}", port = port);
         match TcpStream::connect(address) {
             Ok(stream) => {
+                let reader = std::io::BufReader::new(stream.try_clone()?);
                 let mut jsbsim = JSBSim {
                     connection: stream,
+                    reader,
                     process: Some(process),
+                    root,
+                    dt_sec,
                 };
                 jsbsim.read_line()?;
                 return Ok(jsbsim);
@@ -196,14 +344,19 @@ impl JSBSim {
     }
 
     /// Read one logical response line from the JSBSim console.
+    ///
+    /// Reuses `self.reader` across calls rather than wrapping the connection
+    /// in a fresh `BufReader` each time: a throwaway `BufReader` can read
+    /// ahead into bytes belonging to a later response and then discard them
+    /// when it is dropped, which silently drops replies when several
+    /// commands are pipelined back-to-back (see `get_many`/`set_many`).
     fn read_line(&mut self) -> std::io::Result<String> {
-        let mut reader = std::io::BufReader::new(&self.connection);
         let mut response = String::new();
-        reader.read_line(&mut response)?;
+        self.reader.read_line(&mut response)?;
 
         while response.trim().is_empty() || response.trim() == "JSBSim>" {
             response.clear();
-            reader.read_line(&mut response)?;
+            self.reader.read_line(&mut response)?;
         }
         Ok(response)
     }
@@ -242,6 +395,51 @@ impl JSBSim {
         Ok(())
     }
 
+    /// Run the simulation for `duration` of wall-clock time at `real_time_factor`.
+    ///
+    /// Repeatedly issues `iterate` in small batches sized from the
+    /// simulator's step size, sleeping between batches so that elapsed
+    /// wall-clock time tracks `sim_time / real_time_factor`. This provides
+    /// deterministic real-time pacing without relying on JSBSim's own
+    /// `--realtime` flag, under which JSBSim paces itself and the client no
+    /// longer decides when each step runs.
+    ///
+    /// Returns the real-time factor actually achieved, which will be lower
+    /// than `real_time_factor` if the host could not keep up.
+    pub fn run_for(
+        &mut self,
+        duration: std::time::Duration,
+        real_time_factor: f64,
+    ) -> std::io::Result<f64> {
+        const PACING_TICK_SEC: f64 = 0.05;
+
+        let target_sim_time = duration.as_secs_f64() * real_time_factor;
+        let batch_steps = ((PACING_TICK_SEC / self.dt_sec).round() as i32).max(1);
+
+        let start = std::time::Instant::now();
+        let mut sim_time_elapsed = 0.0;
+
+        while sim_time_elapsed < target_sim_time {
+            let remaining_sim_time = target_sim_time - sim_time_elapsed;
+            let remaining_steps = (remaining_sim_time / self.dt_sec).ceil() as i32;
+            let steps = batch_steps.min(remaining_steps).max(1);
+
+            self.iterate(steps)?;
+            sim_time_elapsed += steps as f64 * self.dt_sec;
+
+            let target_wall_elapsed = sim_time_elapsed / real_time_factor;
+            let wall_elapsed = start.elapsed().as_secs_f64();
+            if target_wall_elapsed > wall_elapsed {
+                std::thread::sleep(std::time::Duration::from_secs_f64(
+                    target_wall_elapsed - wall_elapsed,
+                ));
+            }
+        }
+
+        let wall_elapsed = start.elapsed().as_secs_f64().max(f64::EPSILON);
+        Ok(sim_time_elapsed / wall_elapsed)
+    }
+
     /// Set a simulator property `key` to `value`.
     ///
     /// The function asserts that JSBSim acknowledged the change with
@@ -275,25 +473,90 @@ impl JSBSim {
         self.connection
             .write_all(format!("get {key}\n").as_bytes())?;
         let response = self.read_line()?;
-        let parts = response.trim().split("=");
-        let collection = parts.collect::<Vec<&str>>();
-        debug_assert!(
-            collection.len() == 2,
-            "Response from JSBSim not in expected format '{}' '{}'",
-            collection.len(),
-            response.trim()
-        );
-        collection
-            .get(1)
-            .ok_or_else(|| {
-                GetError::IoError(std::io::Error::new(
-                    std::io::ErrorKind::InvalidData,
-                    "No value returned",
-                ))
-            })?
-            .trim()
-            .parse::<T>()
-            .map_err(GetError::ParseError)
+        parse_property_response(&response)
+    }
+
+    /// Get several properties in one round trip.
+    ///
+    /// All `get` commands are written to the socket in a single burst, then
+    /// the responses are read back and matched up in the same order as
+    /// `keys`. This avoids paying a full round-trip latency per property,
+    /// which dominates when sampling many properties every simulation step.
+    pub fn get_many(&mut self, keys: &[&str]) -> Vec<Result<f64, GetError<f64>>> {
+        use std::io::Write;
+        let mut command = String::new();
+        for key in keys {
+            command.push_str("get ");
+            command.push_str(key);
+            command.push('\n');
+        }
+
+        if let Err(e) = self.connection.write_all(command.as_bytes()) {
+            return keys
+                .iter()
+                .map(|_| Err(GetError::IoError(std::io::Error::new(e.kind(), e.to_string()))))
+                .collect();
+        }
+
+        keys.iter()
+            .map(|_| match self.read_line() {
+                Ok(response) => parse_property_response(&response),
+                Err(e) => Err(GetError::IoError(e)),
+            })
+            .collect()
+    }
+
+    /// Set several properties in one round trip.
+    ///
+    /// All `set` commands are written to the socket in a single burst, then
+    /// each acknowledgement is read back in order. Fails on the first
+    /// property that JSBSim does not acknowledge with `set successful`.
+    pub fn set_many(&mut self, pairs: &[(&str, f64)]) -> std::io::Result<()> {
+        use std::io::Write;
+        let mut command = String::new();
+        for (key, value) in pairs {
+            command.push_str(&format!("set {key} {value}\n"));
+        }
+        self.connection.write_all(command.as_bytes())?;
+
+        // Every `set` above has a reply in flight, so all `pairs.len()`
+        // acknowledgements must be drained even once one fails - otherwise
+        // the unread replies are left in `self.reader` and get misread as
+        // the response to whatever command the caller issues next.
+        let mut first_error = None;
+        for (key, _) in pairs {
+            let line = self.read_line()?;
+            if first_error.is_none() && !line.trim().ends_with("set successful") {
+                first_error = Some(std::io::Error::new(
+                    std::io::ErrorKind::Other,
+                    format!("Failed to set property {key}: {}", line.trim()),
+                ));
+            }
+        }
+        match first_error {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+
+    /// Reset the simulation to the given `InitialConditions`.
+    ///
+    /// Writes `ic` out as a `reset_template.xml` `<initialize>` file in the
+    /// root directory given to `new_with_process` (the current working
+    /// directory for a client made with `new`) and loads it, replacing the aircraft's
+    /// current position and attitude.
+    pub fn reset_to(&mut self, ic: &InitialConditions) -> std::io::Result<()> {
+        let path = self.root.join("reset_template.xml");
+        std::fs::write(&path, ic.to_xml())?;
+        self.send_command(&format!("reset {path}\n", path = path.display()))?;
+        let line = self.read_line()?;
+        if !line.trim().ends_with("Reset successful") {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                format!("Failed to reset: {}", line.trim()),
+            ));
+        }
+        Ok(())
     }
 
     /// Send a raw command string to JSBSim.
@@ -324,6 +587,86 @@ impl Drop for JSBSim {
 mod tests {
     use super::*;
     use serial_test::serial;
+    use std::io::{BufRead, Write};
+    use std::net::TcpListener;
+
+    /// Drives `get_many`/`set_many` against a loopback fake server instead of
+    /// a real JSBSim binary, so it can catch protocol-desync regressions
+    /// (like the one fixed in `set_many`) without needing the simulator.
+    #[test]
+    fn get_many_and_set_many_pipeline_correctly() {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind loopback listener");
+        let addr = listener.local_addr().expect("local addr");
+
+        let server = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().expect("accept connection");
+            let mut writer = stream.try_clone().expect("clone stream");
+            let mut reader = std::io::BufReader::new(stream);
+
+            // The initial connection banner; read_line() skips bare
+            // "JSBSim>" prompt lines waiting for real content, so this must
+            // be a non-prompt line to unblock `JSBSim::new`.
+            writer.write_all(b"JSBSim startup complete\n").unwrap();
+
+            // `JSBSim::new` queries the step size right after connecting.
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            assert_eq!(line.trim(), "get simulation/dt");
+            writer.write_all(b"simulation/dt = 0.0025\n").unwrap();
+
+            // get_many must write all three `get` commands in one burst
+            // before it reads any response.
+            let mut gets = Vec::new();
+            for _ in 0..3 {
+                line.clear();
+                reader.read_line(&mut line).unwrap();
+                gets.push(line.trim().to_string());
+            }
+            assert_eq!(gets, vec!["get a", "get b", "get c"]);
+            // Send all three replies back in a single write so a reader
+            // that reads ahead past one response line would, if broken,
+            // lose the rest.
+            writer.write_all(b"a = 1\nb = 2\nc = 3\n").unwrap();
+
+            let mut sets = Vec::new();
+            for _ in 0..3 {
+                line.clear();
+                reader.read_line(&mut line).unwrap();
+                sets.push(line.trim().to_string());
+            }
+            assert_eq!(sets, vec!["set x 1", "set y 2", "set z 3"]);
+            // The first ack fails; set_many must still drain all three.
+            writer
+                .write_all(b"JSBSim> set failed\nJSBSim> set successful\nJSBSim> set successful\n")
+                .unwrap();
+
+            // A subsequent command must see its own fresh response, not a
+            // leftover ack left over from set_many's drain.
+            line.clear();
+            reader.read_line(&mut line).unwrap();
+            assert_eq!(line.trim(), "get after");
+            writer.write_all(b"after = 4\n").unwrap();
+        });
+
+        let mut jsbsim = JSBSim::new(&addr.to_string()).expect("connect to fake server");
+
+        let values: Vec<f64> = jsbsim
+            .get_many(&["a", "b", "c"])
+            .into_iter()
+            .map(|r| r.expect("get_many value"))
+            .collect();
+        assert_eq!(values, vec![1.0, 2.0, 3.0]);
+
+        let err = jsbsim
+            .set_many(&[("x", 1.0), ("y", 2.0), ("z", 3.0)])
+            .expect_err("first set should fail");
+        assert!(err.to_string().contains('x'));
+
+        let after: f64 = jsbsim.get("after").expect("fresh get after set_many");
+        assert_eq!(after, 4.0);
+
+        server.join().expect("fake server thread panicked");
+    }
 
     #[test]
     #[serial]
@@ -385,5 +728,54 @@ mod tests {
                 .expect("Failed to get time"),
             0.3025
         );
+
+        // reset_to writes a fresh IC file and issues "reset <path>", which
+        // should put the aircraft back at the requested position and
+        // restart simulated time from scratch.
+        let ic = InitialConditions {
+            latitude_deg: 47.449,
+            latitude_kind: LatitudeKind::Geodetic,
+            longitude_deg: -122.311,
+            altitude_ft: 1500.0,
+            heading_deg: 270.0,
+            velocity_kts: None,
+            phi_deg: None,
+            theta_deg: None,
+        };
+        jsbsim
+            .reset_to(&ic)
+            .expect("Failed to reset to initial conditions");
+        assert_eq!(
+            jsbsim
+                .get::<f64>("simulation/sim-time-sec")
+                .expect("Failed to get time"),
+            0.0025
+        );
+        let altitude_ft: f64 = jsbsim
+            .get("position/h-sl-ft")
+            .expect("Failed to get altitude after reset");
+        assert!((altitude_ft - 1500.0).abs() < 1.0);
+
+        // run_for should pace sim time to roughly wall-clock * real_time_factor
+        // and report a correspondingly sane achieved real-time factor.
+        let sim_time_before: f64 = jsbsim
+            .get("simulation/sim-time-sec")
+            .expect("Failed to get time before run_for");
+        let achieved_rtf = jsbsim
+            .run_for(std::time::Duration::from_millis(200), 1.0)
+            .expect("Failed to run_for");
+        let sim_time_after: f64 = jsbsim
+            .get("simulation/sim-time-sec")
+            .expect("Failed to get time after run_for");
+        assert!(
+            (sim_time_after - sim_time_before - 0.2).abs() < 0.05,
+            "expected sim time to advance by ~0.2s, got {}",
+            sim_time_after - sim_time_before
+        );
+        assert!(
+            (0.5..1.5).contains(&achieved_rtf),
+            "achieved real-time factor {} is not close to the requested 1.0",
+            achieved_rtf
+        );
     }
 }